@@ -0,0 +1,7 @@
+//! Small helpers shared by the benchmark binaries in this crate.
+
+/// Pull the value following `flag` out of the CLI args, if present.
+pub fn get_flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let pos = args.iter().position(|a| a == flag)?;
+    Some(args.get(pos + 1)?.as_str())
+}
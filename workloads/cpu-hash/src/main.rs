@@ -1,34 +1,133 @@
+use common::get_flag_value;
 use sha2::{Digest, Sha256};
 use std::env;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::Instant;
 
+const BUFFER_SIZE: usize = 32;
+
 /// CPU-bound workload:
 /// - Take N iterations from CLI (default: 2_000_000)
 /// - Hash a small buffer in a tight loop
+///
+/// Pass `--algo {sha256,blake3}` to pick the hash backend (default sha256).
+/// sha256 hashes a 32-byte buffer; blake3 hashes a 64 KiB buffer instead.
+/// Pass `--difficulty <bits>` to switch to a proof-of-work nonce search
+/// instead: workers race to find a digest with that many leading zero bits.
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let iterations: u64 = if args.len() > 1 {
+
+    if let Some(difficulty) = parse_difficulty(&args) {
+        run_pow_search(difficulty);
+        return;
+    }
+
+    let iterations: u64 = if args.len() > 1 && !args[1].starts_with("--") {
         args[1].parse().unwrap_or(2_000_000)
     } else {
         2_000_000
     };
 
-    const BUFFER_SIZE: usize = 32;
+    match parse_algo(&args) {
+        Algo::Sha256 => run_fixed::<Sha256Backend>(iterations, "sha256"),
+        Algo::Blake3 => run_fixed::<Blake3Backend>(iterations, "blake3"),
+    }
+}
+
+/// Pull `--difficulty <bits>` out of the CLI args, if present.
+fn parse_difficulty(args: &[String]) -> Option<u32> {
+    let value = get_flag_value(args, "--difficulty")?;
+    Some(value.parse().expect("--difficulty value must be an integer"))
+}
+
+/// Hash backend selectable via `--algo`.
+enum Algo {
+    Sha256,
+    Blake3,
+}
+
+/// Pull `--algo {sha256,blake3}` out of the CLI args, defaulting to sha256.
+fn parse_algo(args: &[String]) -> Algo {
+    match get_flag_value(args, "--algo") {
+        None => Algo::Sha256,
+        Some("sha256") => Algo::Sha256,
+        Some("blake3") => Algo::Blake3,
+        Some(other) => panic!("unknown --algo {other} (expected sha256 or blake3)"),
+    }
+}
+
+/// A hashing backend whose hot-loop `update` call is monomorphized per
+/// implementation, so swapping algorithms doesn't cost a vtable indirection.
+trait HashBackend {
+    /// Size of the buffer the caller should feed into `update`. SHA-256 only
+    /// needs a small buffer; blake3's SIMD tree hashing wants wide reads
+    /// (16-64 KiB) to reach peak throughput.
+    const BUFFER_SIZE: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> String;
+}
+
+struct Sha256Backend(Sha256);
+
+impl HashBackend for Sha256Backend {
+    const BUFFER_SIZE: usize = BUFFER_SIZE;
+
+    fn new() -> Self {
+        Sha256Backend(Sha256::new())
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> String {
+        format!("{:x}", self.0.finalize())
+    }
+}
+
+struct Blake3Backend(blake3::Hasher);
+
+impl HashBackend for Blake3Backend {
+    const BUFFER_SIZE: usize = 64 * 1024;
+
+    fn new() -> Self {
+        Blake3Backend(blake3::Hasher::new())
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> String {
+        self.0.finalize().to_hex().to_string()
+    }
+}
+
+/// Hash `iterations` buffers through backend `B`, filling the trailing 8
+/// bytes of each buffer with a running counter, and report throughput.
+fn run_fixed<B: HashBackend>(iterations: u64, algo_name: &str) {
     let prefix: &[u8] = b"cpu-hash-benchmark"; // length != 32, so don't assume
 
-    // Fixed 32-byte buffer, prefix + zero padding
-    let mut data = [0u8; BUFFER_SIZE];
-    let prefix_len = prefix.len();
-    assert!(prefix_len <= BUFFER_SIZE - 8, "prefix too long for buffer");
-    data[..prefix_len].copy_from_slice(prefix);
+    let buffer_size = B::BUFFER_SIZE;
+    assert!(
+        prefix.len() <= buffer_size - 8,
+        "prefix too long for buffer"
+    );
+
+    // Buffer filled with prefix + zero padding + trailing counter.
+    let mut data = vec![0u8; buffer_size];
+    data[..prefix.len()].copy_from_slice(prefix);
 
     let start = Instant::now();
 
-    let mut hasher = Sha256::new();
+    let mut hasher = B::new();
     for i in 0..iterations {
         // Put the counter in the last 8 bytes
-        let counter_bytes = i.to_le_bytes(); // 8 bytes
-        let offset = BUFFER_SIZE - counter_bytes.len(); // 32 - 8 = 24
+        let counter_bytes = i.to_le_bytes();
+        let offset = buffer_size - counter_bytes.len();
         data[offset..].copy_from_slice(&counter_bytes);
 
         hasher.update(&data);
@@ -37,10 +136,106 @@ fn main() {
     let digest = hasher.finalize();
     let elapsed = start.elapsed();
 
+    let bytes_hashed = iterations * buffer_size as u64;
+    let elapsed_s = elapsed.as_secs_f64();
+    let throughput_mb_s = bytes_hashed as f64 / 1e6 / elapsed_s;
+
     println!(
-        "iterations={} digest={:x} elapsed_ms={:.3}",
+        "algo={} iterations={} digest={} bytes_hashed={} elapsed_ms={:.3} throughput_mb_s={:.2}",
+        algo_name,
         iterations,
         digest,
-        elapsed.as_secs_f64() * 1000.0
+        bytes_hashed,
+        elapsed_s * 1000.0,
+        throughput_mb_s
+    );
+}
+
+/// A winning nonce/digest pair found by one of the search workers.
+struct Solution {
+    nonce: u64,
+    digest: String,
+}
+
+/// Count the number of leading zero bits in `digest`.
+fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in digest {
+        if *byte == 0x00 {
+            bits += 8;
+        } else {
+            bits += byte.leading_zeros();
+            break;
+        }
+    }
+    bits
+}
+
+/// Mining-style benchmark: spawn one worker per available core, each
+/// searching its own nonce range for a digest with `difficulty` or more
+/// leading zero bits. First worker to find one wins and all workers stop.
+fn run_pow_search(difficulty: u32) {
+    let num_workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let found = AtomicBool::new(false);
+    let solution: Mutex<Option<Solution>> = Mutex::new(None);
+    let total_hashes = AtomicU64::new(0);
+
+    let start = Instant::now();
+
+    std::thread::scope(|scope| {
+        for worker_id in 0..num_workers {
+            let found = &found;
+            let solution = &solution;
+            let total_hashes = &total_hashes;
+            scope.spawn(move || {
+                // Seed this worker's buffer with its thread index in the
+                // high bytes so each worker searches a disjoint nonce range.
+                let mut data = [0u8; BUFFER_SIZE];
+                data[..8].copy_from_slice(&(worker_id as u64).to_be_bytes());
+
+                let mut counter: u64 = 0;
+                let mut hashed = 0u64;
+
+                loop {
+                    if found.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    data[BUFFER_SIZE - 8..].copy_from_slice(&counter.to_le_bytes());
+
+                    let digest = Sha256::digest(data);
+                    hashed += 1;
+
+                    if leading_zero_bits(&digest) >= difficulty {
+                        if !found.swap(true, Ordering::Relaxed) {
+                            *solution.lock().unwrap() = Some(Solution {
+                                nonce: counter,
+                                digest: format!("{:x}", digest),
+                            });
+                        }
+                        break;
+                    }
+
+                    counter = counter.wrapping_add(1);
+                }
+
+                total_hashes.fetch_add(hashed, Ordering::Relaxed);
+            });
+        }
+    });
+
+    let elapsed = start.elapsed();
+    let total_hashes = total_hashes.load(Ordering::Relaxed);
+    let winner = solution.lock().unwrap().take().expect("no worker found a solution");
+
+    let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+    let hashes_per_sec = total_hashes as f64 / elapsed.as_secs_f64();
+
+    println!(
+        "difficulty={} winning_nonce={} digest={} total_hashes={} elapsed_ms={:.3} hashes_per_sec={:.2}",
+        difficulty, winner.nonce, winner.digest, total_hashes, elapsed_ms, hashes_per_sec
     );
 }
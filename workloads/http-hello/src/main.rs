@@ -1,29 +1,243 @@
+use common::get_flag_value;
+use rustls_pemfile::{certs, pkcs8_private_keys};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::BufReader;
 use std::str::FromStr;
-use std::thread;
-use tiny_http::{Header, Response, Server};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use std::{env, thread};
+use tiny_http::{Header, Response, Server, SslConfig, StatusCode};
+
+/// Shared state the route handlers read and write across request threads.
+struct AppState {
+    state_counter: AtomicU64,
+    metrics: Mutex<HashMap<String, LatencyStats>>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        AppState {
+            state_counter: AtomicU64::new(0),
+            metrics: Mutex::new(HashMap::new()),
+        }
+    }
+}
 
 fn main() {
-    // Bind HTTP server
-    let server = Server::http("0.0.0.0:8080").expect("failed to bind 0.0.0.0:8080");
+    let args: Vec<String> = env::args().collect();
+    let tls = args.iter().any(|a| a == "--tls");
+
+    let server = if tls {
+        let cert_path = get_flag_value(&args, "--cert").expect("--tls requires --cert <path>");
+        let key_path = get_flag_value(&args, "--key").expect("--tls requires --key <path>");
+        let ssl_config = load_tls_config(cert_path, key_path);
 
-    println!("[http-hello] listening on http://0.0.0.0:8080");
+        let server = Server::https("0.0.0.0:8443", ssl_config)
+            .expect("failed to bind 0.0.0.0:8443 with TLS");
+        println!("[http-hello] listening on https://0.0.0.0:8443");
+        server
+    } else {
+        let server = Server::http("0.0.0.0:8080").expect("failed to bind 0.0.0.0:8080");
+        println!("[http-hello] listening on http://0.0.0.0:8080");
+        server
+    };
+
+    let state = Arc::new(AppState::new());
 
     // Handle incoming requests forever
     for request in server.incoming_requests() {
         let url = request.url().to_string();
         let method = request.method().as_str().to_string();
-
-        // Explicitly build a tiny_http::Header
-        let content_type =
-            Header::from_str("Content-Type: text/plain; charset=utf-8").expect("invalid header");
-
-        let response = Response::from_string("hello").with_header(content_type);
+        let state = Arc::clone(&state);
 
         // Offload response to a short-lived thread so we don't block the loop
         thread::spawn(move || {
+            let route = route_for(&method, &url);
+            let start = Instant::now();
+            let (status, body) = handle_route(route, &url, &state);
+            let elapsed_us = start.elapsed().as_micros() as u64;
+
+            state
+                .metrics
+                .lock()
+                .unwrap()
+                .entry(route.to_string())
+                .or_insert_with(LatencyStats::new)
+                .record(elapsed_us);
+
+            // Explicitly build a tiny_http::Header
+            let content_type = Header::from_str("Content-Type: text/plain; charset=utf-8")
+                .expect("invalid header");
+            let response = Response::from_string(body)
+                .with_header(content_type)
+                .with_status_code(status);
+
             if let Err(e) = request.respond(response) {
                 eprintln!("[http-hello] error responding to {} {}: {}", method, url, e);
             }
         });
     }
 }
+
+/// Map a request method+path (path optionally with a query string) onto one
+/// of the routes this server knows how to serve. Only GET is routed; every
+/// other method lands on `/method-not-allowed` regardless of path.
+fn route_for(method: &str, url: &str) -> &'static str {
+    if method != "GET" {
+        return "/method-not-allowed";
+    }
+
+    let path = url.split('?').next().unwrap_or(url);
+    match path {
+        "/" => "/",
+        "/state" => "/state",
+        "/hash" => "/hash",
+        "/health" => "/health",
+        "/metrics" => "/metrics",
+        _ => "/unknown",
+    }
+}
+
+/// Cap on the `n` query param accepted by `/hash`, so a single request can't
+/// force a multi-gigabyte allocation and abort the process.
+const MAX_HASH_BYTES: usize = 8 * 1024 * 1024;
+
+/// Produce the response status and body for `route`, given the full request
+/// URL (so query-string routes like `/hash?n=` can pull their arguments out).
+fn handle_route(route: &str, url: &str, state: &AppState) -> (StatusCode, String) {
+    match route {
+        "/" => (StatusCode(200), "hello".to_string()),
+        "/state" => (
+            StatusCode(200),
+            (state.state_counter.fetch_add(1, Ordering::Relaxed) + 1).to_string(),
+        ),
+        "/hash" => {
+            let n: usize = url
+                .split_once('?')
+                .and_then(|(_, query)| query.split('&').find_map(|kv| kv.strip_prefix("n=")))
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+
+            if n > MAX_HASH_BYTES {
+                return (
+                    StatusCode(400),
+                    format!("n={n} exceeds max of {MAX_HASH_BYTES} bytes"),
+                );
+            }
+
+            let digest = Sha256::digest(vec![0u8; n]);
+            (StatusCode(200), format!("{:x}", digest))
+        }
+        "/health" => (StatusCode(200), "ok".to_string()),
+        "/metrics" => (StatusCode(200), render_metrics(&state.metrics)),
+        "/method-not-allowed" => (StatusCode(405), "method not allowed".to_string()),
+        _ => (StatusCode(404), "not found".to_string()),
+    }
+}
+
+/// Latency bucket boundaries, in microseconds. A sample falls into the first
+/// bucket whose boundary it doesn't exceed; anything above the last boundary
+/// lands in the overflow bucket.
+const HISTOGRAM_BOUNDS_US: &[u64] = &[100, 500, 1_000, 5_000, 10_000, 50_000, 100_000, 500_000];
+
+/// Running count, sum, and bucketed histogram of latencies for one route.
+struct LatencyStats {
+    count: u64,
+    sum_us: u64,
+    buckets: [u64; HISTOGRAM_BOUNDS_US.len() + 1],
+}
+
+impl LatencyStats {
+    fn new() -> Self {
+        LatencyStats {
+            count: 0,
+            sum_us: 0,
+            buckets: [0; HISTOGRAM_BOUNDS_US.len() + 1],
+        }
+    }
+
+    fn record(&mut self, latency_us: u64) {
+        self.count += 1;
+        self.sum_us += latency_us;
+        let bucket = HISTOGRAM_BOUNDS_US
+            .iter()
+            .position(|&bound| latency_us <= bound)
+            .unwrap_or(HISTOGRAM_BOUNDS_US.len());
+        self.buckets[bucket] += 1;
+    }
+
+    /// Approximate percentile, accurate to the nearest histogram bucket.
+    fn percentile(&self, pct: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let target = (pct / 100.0 * self.count as f64).ceil() as u64;
+        let mut cumulative = 0;
+        for (i, &count) in self.buckets.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return *HISTOGRAM_BOUNDS_US.get(i).unwrap_or(&HISTOGRAM_BOUNDS_US[HISTOGRAM_BOUNDS_US.len() - 1]);
+            }
+        }
+        HISTOGRAM_BOUNDS_US[HISTOGRAM_BOUNDS_US.len() - 1]
+    }
+
+    fn avg_us(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_us as f64 / self.count as f64
+        }
+    }
+}
+
+/// Render per-route counts and latency percentiles as plain-text key/value
+/// lines, one route per line.
+fn render_metrics(metrics: &Mutex<HashMap<String, LatencyStats>>) -> String {
+    let metrics = metrics.lock().unwrap();
+    let mut routes: Vec<&String> = metrics.keys().collect();
+    routes.sort();
+
+    let mut out = String::new();
+    for route in routes {
+        let stats = &metrics[route];
+        out.push_str(&format!(
+            "route={} count={} avg_us={:.2} p50_us={} p99_us={}\n",
+            route,
+            stats.count,
+            stats.avg_us(),
+            stats.percentile(50.0),
+            stats.percentile(99.0),
+        ));
+    }
+    out
+}
+
+/// Read and sanity-check the cert chain and private key for `--tls`, then
+/// hand them to tiny_http as its (rustls-backed) `SslConfig`.
+fn load_tls_config(cert_path: &str, key_path: &str) -> SslConfig {
+    let certificate = std::fs::read(cert_path)
+        .unwrap_or_else(|e| panic!("failed to read --cert file {cert_path}: {e}"));
+    let private_key = std::fs::read(key_path)
+        .unwrap_or_else(|e| panic!("failed to read --key file {key_path}: {e}"));
+
+    // tiny_http re-parses these PEM blobs itself via rustls-pemfile when
+    // built with the `ssl-rustls` feature; parse them here too so a bad
+    // --cert/--key fails fast with a clear error instead of inside tiny_http.
+    let chain = certs(&mut BufReader::new(certificate.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("invalid certificate chain in {cert_path}: {e}"));
+    assert!(!chain.is_empty(), "{cert_path} contained no certificates");
+
+    let keys = pkcs8_private_keys(&mut BufReader::new(private_key.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("invalid private key in {key_path}: {e}"));
+    assert!(!keys.is_empty(), "{key_path} contained no PKCS#8 private key");
+
+    SslConfig {
+        certificate,
+        private_key,
+    }
+}
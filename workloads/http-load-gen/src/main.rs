@@ -0,0 +1,115 @@
+use common::get_flag_value;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Concurrent HTTP load generator:
+/// - `--url <url>` target to hammer (required)
+/// - `--connections <C>` worker threads (default: 1)
+/// - `--duration <secs>` run for a fixed wall-clock time, or
+/// - `--requests <N>` run until N total requests have been sent
+///
+/// Drives the http-hello and wasmcloud-http-hello servers in this repo so
+/// the bench suite doesn't need an external load-testing tool.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let url = get_flag_value(&args, "--url")
+        .expect("--url <url> is required")
+        .to_string();
+    let connections: usize = get_flag_value(&args, "--connections")
+        .map(|v| v.parse().expect("--connections must be an integer"))
+        .unwrap_or(1);
+    let duration_secs: Option<f64> = get_flag_value(&args, "--duration")
+        .map(|v| v.parse().expect("--duration must be a number of seconds"));
+    let request_target: Option<u64> = get_flag_value(&args, "--requests")
+        .map(|v| v.parse().expect("--requests must be an integer"));
+
+    assert!(
+        duration_secs.is_some() || request_target.is_some(),
+        "one of --duration <secs> or --requests <N> is required"
+    );
+
+    let deadline = duration_secs.map(|secs| Instant::now() + Duration::from_secs_f64(secs));
+    let requests_sent = AtomicU64::new(0);
+    let latencies_us: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+    let start = Instant::now();
+
+    std::thread::scope(|scope| {
+        for _ in 0..connections {
+            let url = &url;
+            let deadline = deadline;
+            let request_target = request_target;
+            let requests_sent = &requests_sent;
+            let latencies_us = &latencies_us;
+
+            scope.spawn(move || {
+                let mut local_latencies = Vec::new();
+
+                loop {
+                    if let Some(deadline) = deadline {
+                        if Instant::now() >= deadline {
+                            break;
+                        }
+                    }
+                    if let Some(target) = request_target {
+                        if requests_sent.fetch_add(1, Ordering::Relaxed) >= target {
+                            break;
+                        }
+                    } else {
+                        requests_sent.fetch_add(1, Ordering::Relaxed);
+                    }
+
+                    let req_start = Instant::now();
+                    match ureq::get(url).call() {
+                        Ok(response) => {
+                            // Drain the body so the connection can be reused.
+                            let _ = response.into_string();
+                        }
+                        Err(e) => eprintln!("[http-load-gen] request error: {e}"),
+                    }
+                    local_latencies.push(req_start.elapsed().as_micros() as u64);
+                }
+
+                latencies_us.lock().unwrap().extend(local_latencies);
+            });
+        }
+    });
+
+    let elapsed = start.elapsed();
+    let mut latencies_us = latencies_us.into_inner().unwrap();
+    latencies_us.sort_unstable();
+
+    let total_requests = latencies_us.len() as u64;
+    let elapsed_s = elapsed.as_secs_f64();
+    let requests_per_sec = total_requests as f64 / elapsed_s;
+
+    let p50 = percentile(&latencies_us, 50.0);
+    let p90 = percentile(&latencies_us, 90.0);
+    let p99 = percentile(&latencies_us, 99.0);
+    let max = latencies_us.last().copied().unwrap_or(0);
+
+    println!(
+        "total_requests={} elapsed_ms={:.3} requests_per_sec={:.2} p50_us={} p90_us={} p99_us={} max_us={}",
+        total_requests,
+        elapsed_s * 1000.0,
+        requests_per_sec,
+        p50,
+        p90,
+        p99,
+        max
+    );
+}
+
+/// Nearest-rank percentile over an already-sorted latency vector, in
+/// microseconds. Returns 0 for an empty vector.
+fn percentile(sorted_us: &[u64], pct: f64) -> u64 {
+    if sorted_us.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * sorted_us.len() as f64).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted_us.len() - 1);
+    sorted_us[index]
+}
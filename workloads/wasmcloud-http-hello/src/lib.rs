@@ -1,10 +1,12 @@
 use wasmcloud_component::http;
+use wasmcloud_component::wasi::keyvalue::{atomics, store};
 
 struct Component;
 
 http::export!(Component);
 
-static mut COUNTER: u64 = 0;
+/// Key the `/state` counter lives under in the `default` keyvalue bucket.
+const COUNTER_KEY: &str = "http-hello-counter";
 
 impl http::Server for Component {
     fn handle(
@@ -12,12 +14,7 @@ impl http::Server for Component {
     ) -> http::Result<http::Response<impl http::OutgoingBody>> {
         let path = request.uri().path();
         let body = if path.starts_with("/state") {
-            let next = unsafe {
-                // wasm components are single-threaded in this benchmark
-                COUNTER += 1;
-                COUNTER
-            };
-            next.to_string()
+            increment_counter().to_string()
         } else {
             "hello".to_string()
         };
@@ -25,3 +22,35 @@ impl http::Server for Component {
         Ok(http::Response::new(body))
     }
 }
+
+/// Increment the persisted `/state` counter through the wasi-keyvalue
+/// capability, so it survives instance restarts instead of living in
+/// component-local memory. Prefers the host's atomic increment; if that
+/// call fails for this key (e.g. it holds a non-numeric value), falls back
+/// to a read-modify-write against the same bucket.
+fn increment_counter() -> u64 {
+    let bucket = store::open("default").expect("failed to open default keyvalue bucket");
+
+    match atomics::increment(&bucket, COUNTER_KEY, 1) {
+        Ok(next) => next,
+        Err(_) => {
+            // Read-modify-write against the bucket handle's own methods.
+            // Not linearizable under concurrent instances, but matches the
+            // single-instance guarantee the old in-memory counter already
+            // made.
+            let current = bucket
+                .get(COUNTER_KEY)
+                .ok()
+                .flatten()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(0);
+
+            let next = current + 1;
+            bucket
+                .set(COUNTER_KEY, next.to_string().into_bytes())
+                .expect("failed to persist counter");
+            next
+        }
+    }
+}